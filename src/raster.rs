@@ -13,10 +13,72 @@ use alloc::vec;
 use alloc::vec::*;
 use core::cmp::min;
 
+// FreeType's default LCD filter: a 5-tap FIR applied across adjacent subpixels to spread
+// coverage and suppress the color fringing that comes from sampling R/G/B at different
+// horizontal positions. Weights are normalized so they sum to 256.
+const LCD_FILTER_WEIGHTS: [u32; 5] = [0x08, 0x4D, 0x56, 0x4D, 0x08];
+
+// Vectorized prefix sum over the coverage buffer, used by consume_bitmap/get_bitmap. Processes
+// 4 f32 lanes at a time: each lane's local prefix sum is computed with a shuffle-add tree, the
+// scalar carry from prior chunks is added in, and the result is clamped/scaled/packed down to
+// u8 in one pass. SSE2 is baseline on x86_64, so no runtime feature detection is needed; other
+// targets fall back to the scalar loop entirely. Returns the number of elements written, so the
+// caller can finish any remainder with the scalar loop.
+//
+// `src` is read from, and `zero_dst` (when non-null) is the same buffer zeroed in lockstep, so
+// that `consume_bitmap` can pass the same pointer for both while `get_bitmap` passes a null
+// `zero_dst` since it leaves the accumulation buffer untouched.
+#[cfg(target_arch = "x86_64")]
+#[inline]
+unsafe fn scan_bitmap_simd(src: *const f32, zero_dst: *mut f32, dst: &mut [u8], length: usize, acc: &mut f32) -> usize {
+    use core::arch::x86_64::*;
+
+    const LANES: usize = 4;
+    if length < LANES {
+        return 0;
+    }
+    let chunks = length / LANES;
+    let mut carry = _mm_set1_ps(*acc);
+    let ones = _mm_set1_ps(1.0);
+    let sign_mask = _mm_set1_ps(-0.0);
+    let scale = _mm_set1_ps(255.99998);
+
+    for c in 0..chunks {
+        let base = c * LANES;
+        let v = _mm_loadu_ps(src.add(base));
+
+        // local prefix sum within the 4 lanes
+        let v = _mm_add_ps(v, _mm_castsi128_ps(_mm_slli_si128(_mm_castps_si128(v), 4)));
+        let v = _mm_add_ps(v, _mm_castsi128_ps(_mm_slli_si128(_mm_castps_si128(v), 8)));
+        // fold in the running total carried from earlier chunks
+        let v = _mm_add_ps(v, carry);
+
+        if !zero_dst.is_null() {
+            _mm_storeu_ps(zero_dst.add(base), _mm_setzero_ps());
+        }
+
+        // carry this chunk's total (its last lane) forward, broadcast to all lanes
+        carry = _mm_shuffle_ps(v, v, 0xFF);
+
+        let y = _mm_min_ps(_mm_andnot_ps(sign_mask, v), ones);
+        let ints = _mm_cvttps_epi32(_mm_mul_ps(y, scale));
+        let packed16 = _mm_packs_epi32(ints, ints);
+        let packed8 = _mm_packus_epi16(packed16, packed16);
+        let mut bytes = [0u8; 16];
+        _mm_storeu_si128(bytes.as_mut_ptr() as *mut __m128i, packed8);
+        dst.as_mut_ptr().add(base).copy_from_nonoverlapping(bytes.as_ptr(), LANES);
+    }
+
+    *acc = _mm_cvtss_f32(carry);
+    chunks * LANES
+}
+
 pub struct Raster {
     w: usize,
     h: usize,
     a: Vec<f32>,
+    ox: f32,
+    oy: f32,
 }
 
 impl Raster {
@@ -25,9 +87,21 @@ impl Raster {
             w,
             h,
             a: vec![0.0; w * h + 4],
+            ox: 0.0,
+            oy: 0.0,
         }
     }
 
+    /// Sets a fractional pixel offset applied to all geometry rasterized afterwards. This lets a
+    /// glyph be drawn at a quantized subpixel position (e.g. 1/4 or 1/16 of a pixel) instead of
+    /// always snapping to the pixel grid, so a caching layer can key rendered bitmaps on
+    /// (glyph, size, subpixel step) and get noticeably smoother horizontal text layout without
+    /// re-rasterizing every on-screen position from scratch.
+    pub fn set_subpixel_offset(&mut self, x: f32, y: f32) {
+        self.ox = x;
+        self.oy = y;
+    }
+
     pub fn refit(&mut self, w: usize, h: usize) {
         if w * h >= self.a.len() {
             panic!("Given width ({}) and height ({}) exceed the raster's range ({}).", w, h, self.a.len());
@@ -52,24 +126,26 @@ impl Raster {
     }
 
     pub fn draw_line(&mut self, p0: &Point, p1: &Point) {
-        if p0.y == p1.y {
+        let (p0x, p0y) = (p0.x + self.ox, p0.y + self.oy);
+        let (p1x, p1y) = (p1.x + self.ox, p1.y + self.oy);
+        if p0y == p1y {
             return;
         }
-        let (dir, p0, p1) = if p0.y < p1.y {
-            (1.0, p0, p1)
+        let (dir, p0x, p0y, p1x, p1y) = if p0y < p1y {
+            (1.0, p0x, p0y, p1x, p1y)
         } else {
-            (-1.0, p1, p0)
+            (-1.0, p1x, p1y, p0x, p0y)
         };
-        let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
-        let mut x = p0.x;
+        let dxdy = (p1x - p0x) / (p1y - p0y);
+        let mut x = p0x;
         // note: implicit max of 0 because usize (TODO: really true?)
-        let y0 = p0.y as usize;
-        if p0.y < 0.0 {
-            x -= p0.y * dxdy;
+        let y0 = p0y as usize;
+        if p0y < 0.0 {
+            x -= p0y * dxdy;
         }
-        for y in y0..min(self.h, p1.y.ceil() as usize) {
+        for y in y0..min(self.h, p1y.ceil() as usize) {
             let linestart = (y * self.w) as i32;
-            let dy = ((y + 1) as f32).min(p1.y) - (y as f32).max(p0.y);
+            let dy = ((y + 1) as f32).min(p1y) - (y as f32).max(p0y);
             let xnext = x + dxdy * dy;
             let d = dy * dir;
             let (x0, x1) = if x < xnext {
@@ -131,11 +207,193 @@ impl Raster {
         self.draw_line(&p, p2);
     }
 
+    /// Like `draw`, but accumulates coverage into a sub-rectangle of a larger, caller-owned
+    /// buffer instead of this raster's own `a` buffer. `x_offset`/`y_offset` place the glyph's
+    /// origin within `atlas`, and `stride` is the atlas's row width in f32 coverage cells. This
+    /// avoids a per-glyph allocation and a separate blit step when packing many glyphs into a
+    /// texture atlas, which otherwise dominates the cost of rendering whole codepoint ranges.
+    ///
+    /// Just like `Raster`'s own `a` buffer is sized `w * h + 4` to absorb a closing signed-area
+    /// delta that can legitimately land one column past the glyph's right edge, `atlas` must
+    /// reserve a guard column past `x_offset + w` for every row this glyph occupies (i.e.
+    /// `stride >= x_offset + w + 1`). That guard column is never part of a neighboring glyph's
+    /// rectangle, so the overflow can't bleed sideways; `extract_atlas_bitmap` absorbs it back
+    /// out without ever emitting it.
+    ///
+    /// The accumulated coverage must later be read out with `extract_atlas_bitmap`, which also
+    /// zeroes it back out for reuse.
+    pub fn draw_atlas(&self, geometry: &Geometry, atlas: &mut [f32], x_offset: usize, y_offset: usize, stride: usize) {
+        if geometry.is_line() {
+            self.draw_line_atlas(&geometry.a, &geometry.b, atlas, x_offset, y_offset, stride);
+        } else {
+            self.draw_curve_atlas(&geometry.a, &geometry.b, &geometry.c, atlas, x_offset, y_offset, stride);
+        }
+    }
+
+    #[inline(always)]
+    fn add_atlas(atlas: &mut [f32], index: i32, value: f32) {
+        atlas[index as usize] += value;
+    }
+
+    fn draw_line_atlas(&self, p0: &Point, p1: &Point, atlas: &mut [f32], x_offset: usize, y_offset: usize, stride: usize) {
+        let (p0x, p0y) = (p0.x + self.ox, p0.y + self.oy);
+        let (p1x, p1y) = (p1.x + self.ox, p1.y + self.oy);
+        if p0y == p1y {
+            return;
+        }
+        let (dir, p0x, p0y, p1x, p1y) = if p0y < p1y {
+            (1.0, p0x, p0y, p1x, p1y)
+        } else {
+            (-1.0, p1x, p1y, p0x, p0y)
+        };
+        let dxdy = (p1x - p0x) / (p1y - p0y);
+        let mut x = p0x;
+        let y0 = p0y as usize;
+        if p0y < 0.0 {
+            x -= p0y * dxdy;
+        }
+        for y in y0..min(self.h, p1y.ceil() as usize) {
+            let row_start = ((y + y_offset) * stride + x_offset) as i32;
+            let dy = ((y + 1) as f32).min(p1y) - (y as f32).max(p0y);
+            let xnext = x + dxdy * dy;
+            let d = dy * dir;
+            let (x0, x1) = if x < xnext {
+                (x, xnext)
+            } else {
+                (xnext, x)
+            };
+            let x0floor = x0.floor();
+            let x0i = x0floor as i32;
+            let x1ceil = x1.ceil();
+            let x1i = x1ceil as i32;
+            if x1i <= x0i + 1 {
+                let xmf = 0.5 * (x + xnext) - x0floor;
+                Self::add_atlas(atlas, row_start + x0i, d - d * xmf);
+                if xmf != 0.0 {
+                    Self::add_atlas(atlas, row_start + x0i + 1, d * xmf);
+                }
+            } else {
+                let s = (x1 - x0).recip();
+                let x0f = x0 - x0floor;
+                let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+                let x1f = x1 - x1ceil + 1.0;
+                let am = 0.5 * s * x1f * x1f;
+                Self::add_atlas(atlas, row_start + x0i, d * a0);
+                if x1i == x0i + 2 {
+                    Self::add_atlas(atlas, row_start + x0i + 1, d * (1.0 - a0 - am));
+                } else {
+                    let a1 = s * (1.5 - x0f);
+                    Self::add_atlas(atlas, row_start + x0i + 1, d * (a1 - a0));
+                    for xi in x0i + 2..x1i - 1 {
+                        Self::add_atlas(atlas, row_start + xi, d * s);
+                    }
+                    let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+                    Self::add_atlas(atlas, row_start + x1i - 1, d * (1.0 - a2 - am));
+                }
+                Self::add_atlas(atlas, row_start + x1i, d * am);
+            }
+            x = xnext;
+        }
+    }
+
+    fn draw_curve_atlas(&self, p0: &Point, p1: &Point, p2: &Point, atlas: &mut [f32], x_offset: usize, y_offset: usize, stride: usize) {
+        let devx = p0.x - 2.0 * p1.x + p2.x;
+        let devy = p0.y - 2.0 * p1.y + p2.y;
+        let devsq = devx * devx + devy * devy;
+        if devsq < 0.333 {
+            self.draw_line_atlas(p0, p2, atlas, x_offset, y_offset, stride);
+            return;
+        }
+        let tol = 3.0;
+        let n = 1 + (tol * (devx * devx + devy * devy)).sqrt().sqrt().floor() as usize;
+        let mut p = *p0;
+        let nrecip = (n as f32).recip();
+        let mut t = 0.0;
+        for _i in 0..n - 1 {
+            t += nrecip;
+            let pn = Point::lerp(t, &Point::lerp(t, p0, p1), &Point::lerp(t, p1, p2));
+            self.draw_line_atlas(&p, &pn, atlas, x_offset, y_offset, stride);
+            p = pn;
+        }
+        self.draw_line_atlas(&p, p2, atlas, x_offset, y_offset, stride);
+    }
+
+    /// Reads the coverage this raster accumulated into `atlas` (via `draw_atlas`) back out as an
+    /// 8-bit bitmap written into `output` at the same `(x_offset, y_offset)` rectangle, and
+    /// zeroes the consumed region of `atlas` so it can be reused for the next glyph. `stride` and
+    /// `out_stride` are the row widths, in cells, of `atlas` and `output` respectively. Unlike
+    /// `consume_bitmap`, the running accumulator is reset at the start of every row, since rows
+    /// of an atlas sub-rectangle aren't contiguous in memory.
+    ///
+    /// Each row's guard column (`atlas` column `w`, past the glyph's `[0, w)`) is folded into the
+    /// accumulator and zeroed like every other column, but it is never emitted to `output`: it
+    /// only exists to receive a closing signed-area delta that can legitimately land there, the
+    /// same overflow `Raster`'s own `+4`-padded `a` buffer absorbs for the owned-buffer path.
+    pub fn extract_atlas_bitmap(&self, atlas: &mut [f32], output: &mut [u8], x_offset: usize, y_offset: usize, stride: usize, out_stride: usize) {
+        for row in 0..self.h {
+            let row_start = (row + y_offset) * stride + x_offset;
+            let out_start = (row + y_offset) * out_stride + x_offset;
+            let mut acc = 0.0;
+            for col in 0..=self.w {
+                acc += atlas[row_start + col];
+                atlas[row_start + col] = 0.0;
+                if col == self.w {
+                    break;
+                }
+                let y = acc.abs();
+                let y = if y < 1.0 {
+                    y
+                } else {
+                    1.0
+                };
+                output[out_start + col] = (255.99998 * y) as u8;
+            }
+        }
+    }
+
     pub fn consume_bitmap(&mut self) -> Vec<u8> {
         let length = self.w * self.h;
         let mut acc = 0.0;
         let mut output = Vec::with_capacity(length);
         unsafe { output.set_len(length) };
+
+        #[cfg(target_arch = "x86_64")]
+        let done = unsafe { scan_bitmap_simd(self.a.as_ptr(), self.a.as_mut_ptr(), &mut output, length, &mut acc) };
+        #[cfg(not(target_arch = "x86_64"))]
+        let done = 0;
+
+        for i in done..length {
+            unsafe {
+                acc += self.a.get_unchecked(i);
+                *self.a.get_unchecked_mut(i) = 0.0;
+            }
+            let y = acc.abs();
+            let y = if y < 1.0 {
+                y
+            } else {
+                1.0
+            };
+            unsafe {
+                *(output.get_unchecked_mut(i)) = (255.99998 * y) as u8;
+            }
+        }
+        output
+    }
+
+    /// Like `consume_bitmap`, but raises the accumulated coverage to `1.0 / gamma` before
+    /// quantizing it to u8. Coverage quantized directly, as `consume_bitmap` does, makes light
+    /// text on dark backgrounds look too thin and dark text on light backgrounds look too heavy
+    /// once composited in sRGB; this lets callers opt into a gamma correction that keeps
+    /// on-screen blending visually correct. A `gamma` of `1.0` reproduces `consume_bitmap`'s
+    /// output, up to float rounding: on x86_64 `consume_bitmap` sums coverage four lanes at a
+    /// time while this sums it one element at a time, so a pixel on a quantization boundary can
+    /// come out one step apart.
+    pub fn consume_bitmap_gamma(&mut self, gamma: f32) -> Vec<u8> {
+        let length = self.w * self.h;
+        let mut acc = 0.0;
+        let mut output = Vec::with_capacity(length);
+        unsafe { output.set_len(length) };
+        let gamma_recip = gamma.recip();
         for i in 0..length {
             unsafe {
                 acc += self.a.get_unchecked(i);
@@ -147,6 +405,7 @@ impl Raster {
             } else {
                 1.0
             };
+            let y = y.powf(gamma_recip);
             unsafe {
                 *(output.get_unchecked_mut(i)) = (255.99998 * y) as u8;
             }
@@ -154,11 +413,109 @@ impl Raster {
         output
     }
 
+    /// Like `consume_bitmap`, but remaps each quantized coverage byte through a caller-provided
+    /// 256-entry lookup table. Cheaper than `consume_bitmap_gamma` when the correction curve is
+    /// known ahead of time, since it trades the per-pixel `powf` for a table lookup.
+    pub fn consume_bitmap_lut(&mut self, lut: &[u8; 256]) -> Vec<u8> {
+        let mut output = self.consume_bitmap();
+        for byte in output.iter_mut() {
+            *byte = lut[*byte as usize];
+        }
+        output
+    }
+
+    /// Consumes the accumulated coverage as an LCD-filtered, subpixel-antialiased RGB bitmap.
+    ///
+    /// This assumes the raster was sized and drawn at 3x horizontal resolution (one subpixel
+    /// per R/G/B stripe of the target display), which the caller is responsible for setting up
+    /// by scaling the glyph geometry before calling `draw`/`draw_line`/`draw_curve`. The signed-
+    /// area accumulation itself is unchanged; only extraction differs from `consume_bitmap`.
+    ///
+    /// FreeType's default 5-tap FIR filter is convolved over each row of subpixel coverage so
+    /// that every output pixel's R, G and B channel is a weighted blend of its neighboring
+    /// subpixels, which is what keeps color fringing down on RGB-stripe LCD panels. Filter taps
+    /// that fall outside a row are clamped to the nearest edge subpixel. The returned buffer is
+    /// `self.w / 3` pixels wide, 3 bytes (RGB) per pixel.
+    pub fn consume_lcd_bitmap(&mut self) -> Vec<u8> {
+        let length = self.w * self.h;
+        let mut coverage = Vec::with_capacity(length);
+        unsafe { coverage.set_len(length) };
+        let mut acc = 0.0;
+        for i in 0..length {
+            unsafe {
+                acc += self.a.get_unchecked(i);
+                *self.a.get_unchecked_mut(i) = 0.0;
+            }
+            let y = acc.abs();
+            let y = if y < 1.0 {
+                y
+            } else {
+                1.0
+            };
+            unsafe {
+                *coverage.get_unchecked_mut(i) = y;
+            }
+        }
+
+        let out_w = self.w / 3;
+        let mut output = Vec::with_capacity(out_w * self.h * 3);
+        for row in 0..self.h {
+            let row_start = row * self.w;
+            let row_cov = &coverage[row_start..row_start + self.w];
+            for x in 0..out_w {
+                let center = (x * 3) as i32;
+                for channel in 0..3 {
+                    let tap = center + channel;
+                    let mut sum = 0.0;
+                    for (k, &weight) in LCD_FILTER_WEIGHTS.iter().enumerate() {
+                        let offset = (tap + k as i32 - 2).clamp(0, row_cov.len() as i32 - 1);
+                        sum += row_cov[offset as usize] * weight as f32;
+                    }
+                    output.push((255.99998 * (sum / 256.0)) as u8);
+                }
+            }
+        }
+        output
+    }
+
     pub fn get_bitmap(&self) -> Vec<u8> {
         let length = self.w * self.h;
         let mut acc = 0.0;
         let mut output = Vec::with_capacity(length);
         unsafe { output.set_len(length) };
+
+        #[cfg(target_arch = "x86_64")]
+        let done = unsafe { scan_bitmap_simd(self.a.as_ptr(), core::ptr::null_mut(), &mut output, length, &mut acc) };
+        #[cfg(not(target_arch = "x86_64"))]
+        let done = 0;
+
+        for i in done..length {
+            unsafe {
+                acc += self.a.get_unchecked(i);
+            }
+            let y = acc.abs();
+            let y = if y < 1.0 {
+                y
+            } else {
+                1.0
+            };
+            unsafe {
+                *(output.get_unchecked_mut(i)) = (255.99998 * y) as u8;
+            }
+        }
+        output
+    }
+
+    /// Like `get_bitmap`, but raises the accumulated coverage to `1.0 / gamma` before quantizing
+    /// it to u8. See `consume_bitmap_gamma` for why this matters for on-screen blending. A
+    /// `gamma` of `1.0` reproduces `get_bitmap`'s output, up to the same float rounding
+    /// difference described there.
+    pub fn get_bitmap_gamma(&self, gamma: f32) -> Vec<u8> {
+        let length = self.w * self.h;
+        let mut acc = 0.0;
+        let mut output = Vec::with_capacity(length);
+        unsafe { output.set_len(length) };
+        let gamma_recip = gamma.recip();
         for i in 0..length {
             unsafe {
                 acc += self.a.get_unchecked(i);
@@ -169,10 +526,163 @@ impl Raster {
             } else {
                 1.0
             };
+            let y = y.powf(gamma_recip);
             unsafe {
                 *(output.get_unchecked_mut(i)) = (255.99998 * y) as u8;
             }
         }
         output
     }
+
+    /// Like `get_bitmap`, but remaps each quantized coverage byte through a caller-provided
+    /// 256-entry lookup table. See `consume_bitmap_lut`.
+    pub fn get_bitmap_lut(&self, lut: &[u8; 256]) -> Vec<u8> {
+        let mut output = self.get_bitmap();
+        for byte in output.iter_mut() {
+            *byte = lut[*byte as usize];
+        }
+        output
+    }
+}
+
+// `Font`-level plumbing for these options (exposing `set_subpixel_offset`, `draw_atlas`/
+// `extract_atlas_bitmap`, `consume_lcd_bitmap`, and the gamma/LUT bitmap variants through the
+// public glyph-rasterization API) belongs in font.rs; these tests exercise `Raster` directly.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pt(x: f32, y: f32) -> Point {
+        Point { x, y }
+    }
+
+    #[test]
+    fn lcd_bitmap_is_one_third_width_rgb() {
+        let w = 9;
+        let h = 4;
+        let mut r = Raster::new(w, h);
+        r.draw_line(&pt(4.0, 0.0), &pt(4.0, h as f32));
+        let out = r.consume_lcd_bitmap();
+        assert_eq!(out.len(), (w / 3) * h * 3);
+    }
+
+    #[test]
+    fn lcd_filter_applies_to_float_coverage_with_expected_weighting() {
+        // a single fully-covered subpixel column (index 4, the middle pixel's G subpixel) with
+        // every other subpixel at zero coverage, so each output channel's value is exactly one
+        // FIR tap weight (scaled to a u8), letting us check the filter's actual weighting instead
+        // of just the output buffer's length.
+        let w = 9;
+        let h = 1;
+        let mut r = Raster::new(w, h);
+        r.draw_line(&pt(4.0, 0.0), &pt(4.0, h as f32));
+        r.draw_line(&pt(5.0, h as f32), &pt(5.0, 0.0));
+        let out = r.consume_lcd_bitmap();
+        assert_eq!(out.len(), 3 * 3);
+
+        let (r0, g0, b0) = (out[0], out[1], out[2]);
+        let (r1, g1, b1) = (out[3], out[4], out[5]);
+        let (r2, g2, b2) = (out[6], out[7], out[8]);
+
+        // the center pixel sits squarely on the stem: its G channel (the center tap) outweighs
+        // its symmetric R and B taps, which are equally weighted on either side.
+        assert_eq!(r1, b1);
+        assert!(g1 > r1);
+        assert!(r1 > 0);
+
+        // the stem only bleeds one tap into each neighboring pixel, symmetrically: the right edge
+        // tap of pixel 0 and the left edge tap of pixel 2 pick up the same outermost filter weight.
+        assert_eq!(b0, r2);
+        assert!(b0 > 0);
+        assert_eq!(r0, 0);
+        assert_eq!(g0, 0);
+        assert_eq!(g2, 0);
+        assert_eq!(b2, 0);
+    }
+
+    #[test]
+    fn subpixel_offset_shifts_rasterized_coverage() {
+        let w = 8;
+        let h = 4;
+
+        let mut unshifted = Raster::new(w, h);
+        unshifted.draw_line(&pt(2.0, 0.0), &pt(2.0, h as f32));
+        let unshifted_bitmap = unshifted.consume_bitmap();
+
+        let mut shifted = Raster::new(w, h);
+        shifted.set_subpixel_offset(1.0, 0.0);
+        shifted.draw_line(&pt(2.0, 0.0), &pt(2.0, h as f32));
+        let shifted_bitmap = shifted.consume_bitmap();
+
+        assert_ne!(unshifted_bitmap, shifted_bitmap);
+        // shifting by exactly one whole pixel should reproduce the unshifted coverage pattern,
+        // moved one column to the right.
+        for row in 0..h {
+            assert_eq!(unshifted_bitmap[row * w..row * w + w - 1], shifted_bitmap[row * w + 1..row * w + w]);
+        }
+    }
+
+    #[test]
+    fn atlas_draw_matches_owned_buffer_draw() {
+        let w = 8;
+        let h = 4;
+
+        // a closed box spanning columns [6, 8), touching the raster's right edge: both edges'
+        // deltas net to zero across a row, so consume_bitmap's single carried-forward accumulator
+        // and extract_atlas_bitmap's per-row-reset accumulator agree on every row.
+        let mut direct = Raster::new(w, h);
+        direct.draw_line(&pt(6.0, 0.0), &pt(6.0, h as f32));
+        direct.draw_line(&pt(8.0, h as f32), &pt(8.0, 0.0));
+        let direct_bitmap = direct.consume_bitmap();
+
+        // a guard column so the box's right-edge closing delta, which lands at column w, has
+        // somewhere to go other than folding into column w - 1.
+        let stride = w + 1;
+        let mut atlas = vec![0.0f32; stride * h];
+        let mut output = vec![0u8; stride * h];
+        let via_atlas = Raster::new(w, h);
+        via_atlas.draw_line_atlas(&pt(6.0, 0.0), &pt(6.0, h as f32), &mut atlas, 0, 0, stride);
+        via_atlas.draw_line_atlas(&pt(8.0, h as f32), &pt(8.0, 0.0), &mut atlas, 0, 0, stride);
+        via_atlas.extract_atlas_bitmap(&mut atlas, &mut output, 0, 0, stride, stride);
+
+        for row in 0..h {
+            assert_eq!(direct_bitmap[row * w..row * w + w], output[row * stride..row * stride + w]);
+        }
+    }
+
+    #[test]
+    fn gamma_of_one_reproduces_consume_bitmap() {
+        let w = 6;
+        let h = 3;
+
+        let mut plain = Raster::new(w, h);
+        plain.draw_line(&pt(2.0, 0.0), &pt(4.0, h as f32));
+        let plain_bitmap = plain.consume_bitmap();
+
+        let mut gamma = Raster::new(w, h);
+        gamma.draw_line(&pt(2.0, 0.0), &pt(4.0, h as f32));
+        let gamma_bitmap = gamma.consume_bitmap_gamma(1.0);
+
+        assert_eq!(plain_bitmap, gamma_bitmap);
+    }
+
+    #[test]
+    fn identity_lut_reproduces_consume_bitmap() {
+        let w = 6;
+        let h = 3;
+
+        let mut plain = Raster::new(w, h);
+        plain.draw_line(&pt(2.0, 0.0), &pt(4.0, h as f32));
+        let plain_bitmap = plain.consume_bitmap();
+
+        let mut identity = [0u8; 256];
+        for (i, entry) in identity.iter_mut().enumerate() {
+            *entry = i as u8;
+        }
+        let mut via_lut = Raster::new(w, h);
+        via_lut.draw_line(&pt(2.0, 0.0), &pt(4.0, h as f32));
+        let lut_bitmap = via_lut.consume_bitmap_lut(&identity);
+
+        assert_eq!(plain_bitmap, lut_bitmap);
+    }
 }